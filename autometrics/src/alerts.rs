@@ -1,4 +1,7 @@
+use std::collections::BTreeMap;
+
 use linkme::distributed_slice;
+use serde::Serialize;
 
 // This "distributed slice" is used to collect all the alerts defined when a
 // call to the `autometrics` macro has the `alerts` argument.
@@ -14,6 +17,54 @@ pub struct Alert {
     pub module: &'static str,
     pub success_rate: Option<&'static str>,
     pub latency: Option<(&'static str, &'static str)>,
+    /// Static user-defined labels (e.g. `owner`, `team`, `notification_channel`)
+    /// merged into every generated rule, for Alertmanager routing and
+    /// ownership-keyed dashboards.
+    pub labels: &'static [(&'static str, &'static str)],
+    /// The name of the alerting method to use for this function, overriding the
+    /// [`AlertsConfig`] default. `None` uses the configured default.
+    pub alert_method: Option<&'static str>,
+    /// Optional HTTP status-code classification. When set, the error and total
+    /// queries filter on a status-code label instead of treating every
+    /// `result="error"` as a failure. `None` keeps the default behaviour.
+    pub code: Option<CodeClassification>,
+}
+
+/// Describes how HTTP status codes map onto the SLI for a web service.
+///
+/// Without this, `SuccessRateObjective` counts any `result="error"` as a
+/// failure and `LatencyObjective` counts every request toward the denominator.
+/// Real services want to classify by response code — for example counting only
+/// `5xx` as errors, while excluding `4xx` client errors (or just `429`) from
+/// the budget entirely.
+#[derive(Clone, Copy)]
+pub struct CodeClassification {
+    /// The label carrying the status code, e.g. `status_code` or `code`.
+    pub label: &'static str,
+    /// A regex matcher (`=~`) for the codes that count as errors, e.g. `5..`.
+    pub error_matcher: &'static str,
+    /// An optional regex matcher (`!~`) for codes that should not count toward
+    /// the SLI at all, e.g. `4..` or `429`.
+    pub exclude_matcher: Option<&'static str>,
+}
+
+impl CodeClassification {
+    /// The selector fragment excluding codes that shouldn't count at all.
+    /// Empty when no exclusion matcher is configured.
+    fn exclusion_selector(&self) -> String {
+        match self.exclude_matcher {
+            Some(matcher) => format!(",{}!~\"{}\"", self.label, matcher),
+            None => String::new(),
+        }
+    }
+
+    /// The selector fragment for latency SLIs. Like the libsonnet `code!~"5.."`
+    /// reference, error responses (`error_matcher`) are excluded from both the
+    /// latency numerator and denominator — we don't hold failed requests to the
+    /// latency objective — on top of any codes that don't count at all.
+    fn latency_selector(&self) -> String {
+        format!(",{}!~\"{}\"{}", self.label, self.error_matcher, self.exclusion_selector())
+    }
 }
 
 impl Alert {
@@ -24,6 +75,9 @@ impl Alert {
                 function: self.function,
                 module: self.module,
                 success_rate,
+                labels: self.labels,
+                alert_method: self.alert_method,
+                code: self.code,
             }));
         }
         if let Some((latency_threshold, latency_objective)) = self.latency {
@@ -32,44 +86,296 @@ impl Alert {
                 module: self.module,
                 latency_threshold,
                 latency_objective,
+                labels: self.labels,
+                alert_method: self.alert_method,
+                code: self.code,
             }));
         }
         objectives.into_iter()
     }
 }
 
-/// Returns the Prometheus recording and alerting rules as a YAML string.
+/// A single burn-rate condition: the error rate measured over both a `short`
+/// and a `long` window must exceed `factor` times the error budget for the
+/// alert to fire. The `single-window` method checks the `short` window only.
+#[derive(Clone)]
+pub struct BurnRate {
+    pub short_window: &'static str,
+    pub long_window: &'static str,
+    pub factor: f64,
+}
+
+/// A severity tier of the alerting ladder. All of the tier's [`BurnRate`]s are
+/// OR-joined into a single alert carrying the `severity` label.
+#[derive(Clone)]
+pub struct SeverityTier {
+    pub severity: &'static str,
+    pub burn_rates: Vec<BurnRate>,
+}
+
+/// Controls how the recording and alerting rules are generated.
 ///
-/// To generate alerts, add the `alerts` parameter to the `autometrics` macro
-/// for at least one function.
+/// Teams run different error-budget policies, so the rolling period, the
+/// recording windows, and the burn-rate ladder are all configurable. Use
+/// [`AlertsConfig::builder`] to override individual values, or
+/// [`AlertsConfig::default`] for the 30-day, Google-SRE-style 14.4/6/3/1
+/// ladder that [`generate_alerts`] uses.
+#[derive(Clone)]
+pub struct AlertsConfig {
+    /// The rolling period the error budget is measured over, in days.
+    pub period_days: u32,
+    /// The windows that error-ratio recording rules are emitted for.
+    pub windows: Vec<&'static str>,
+    /// The burn-rate ladder, one entry per severity.
+    pub severity_tiers: Vec<SeverityTier>,
+    /// The evaluation cadence applied to each generated rule group. `None`
+    /// leaves it up to Prometheus' global `evaluation_interval`.
+    pub interval: Option<&'static str>,
+    /// The name of the [`AlertMethod`] used to turn the burn-rate ladder into
+    /// alerting rules. Defaults to `"multi-window"`; an objective may override
+    /// it through the `autometrics` macro.
+    pub method: &'static str,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        AlertsConfig {
+            period_days: 30,
+            windows: vec!["5m", "30m", "1h", "2h", "6h", "1d", "3d"],
+            severity_tiers: vec![
+                SeverityTier {
+                    severity: "page",
+                    burn_rates: vec![
+                        BurnRate {
+                            short_window: "5m",
+                            long_window: "1h",
+                            factor: 14.4,
+                        },
+                        BurnRate {
+                            short_window: "30m",
+                            long_window: "6h",
+                            factor: 6.0,
+                        },
+                    ],
+                },
+                SeverityTier {
+                    severity: "ticket",
+                    burn_rates: vec![
+                        BurnRate {
+                            short_window: "2h",
+                            long_window: "1d",
+                            factor: 3.0,
+                        },
+                        BurnRate {
+                            short_window: "6h",
+                            long_window: "3d",
+                            factor: 1.0,
+                        },
+                    ],
+                },
+            ],
+            interval: None,
+            method: DEFAULT_ALERT_METHOD,
+        }
+    }
+}
+
+impl AlertsConfig {
+    /// Start building a custom configuration.
+    pub fn builder() -> AlertsConfigBuilder {
+        AlertsConfigBuilder {
+            config: AlertsConfig::default(),
+        }
+    }
+
+    /// The rolling period as a Prometheus range selector, e.g. `30d`.
+    fn period(&self) -> String {
+        format!("{}d", self.period_days)
+    }
+
+    /// The base window, used for the long-period average and the
+    /// current burn rate. The long-period and `current_burn_rate` rules build on
+    /// this window's recording rule, so it must be one of [`windows`]; we take
+    /// the first entry (falling back to `5m` if the list is empty).
+    ///
+    /// [`windows`]: AlertsConfig::windows
+    fn base_window(&self) -> &'static str {
+        self.windows.first().copied().unwrap_or("5m")
+    }
+}
+
+/// Builder for [`AlertsConfig`], starting from the defaults.
+pub struct AlertsConfigBuilder {
+    config: AlertsConfig,
+}
+
+impl AlertsConfigBuilder {
+    /// Override the rolling error-budget period, in days.
+    pub fn period_days(mut self, days: u32) -> Self {
+        self.config.period_days = days;
+        self
+    }
+
+    /// Override the windows that error-ratio recording rules are emitted for.
+    pub fn windows(mut self, windows: Vec<&'static str>) -> Self {
+        self.config.windows = windows;
+        self
+    }
+
+    /// Override the burn-rate ladder.
+    pub fn severity_tiers(mut self, severity_tiers: Vec<SeverityTier>) -> Self {
+        self.config.severity_tiers = severity_tiers;
+        self
+    }
+
+    /// Override the evaluation cadence applied to each rule group.
+    pub fn interval(mut self, interval: Option<&'static str>) -> Self {
+        self.config.interval = interval;
+        self
+    }
+
+    /// Override the alerting method, by name (see [`AlertMethod`]).
+    pub fn method(mut self, method: &'static str) -> Self {
+        self.config.method = method;
+        self
+    }
+
+    /// Finish building the configuration.
+    pub fn build(self) -> AlertsConfig {
+        self.config
+    }
+}
+
+/// A Prometheus [rule group](https://prometheus.io/docs/prometheus/latest/configuration/recording_rules/#rule_group).
+#[derive(Serialize)]
+pub struct RuleGroup {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<String>,
+    pub rules: Vec<Rule>,
+}
+
+/// A single Prometheus recording or alerting rule.
 ///
-/// Then, call this function to generate the Prometheus rules. You will need
-/// to output the rules to a file and
-/// [load them into Prometheus](https://prometheus.io/docs/prometheus/latest/configuration/recording_rules/).
-pub fn generate_alerts() -> String {
-    let groups = METRICS
+/// Building these as typed values rather than templated strings means label
+/// values are escaped by the serializer and the indentation can't drift.
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum Rule {
+    Record {
+        record: String,
+        expr: String,
+        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+        labels: BTreeMap<String, String>,
+    },
+    Alert {
+        alert: String,
+        expr: String,
+        #[serde(rename = "for", skip_serializing_if = "Option::is_none")]
+        for_: Option<String>,
+        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+        labels: BTreeMap<String, String>,
+        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+        annotations: BTreeMap<String, String>,
+    },
+}
+
+/// The top-level rules document Prometheus loads.
+#[derive(Serialize)]
+struct RuleDocument {
+    groups: Vec<RuleGroup>,
+}
+
+fn rule_groups(config: &AlertsConfig) -> Vec<RuleGroup> {
+    METRICS
         .iter()
         .flat_map(|alert| {
             alert.to_objectives().flat_map(|objective| {
                 [
-                    objective.error_ratio_recording_rules(),
-                    objective.meta_recording_rules(),
-                    objective.alert_rules(),
+                    objective.error_ratio_recording_rules(config),
+                    objective.meta_recording_rules(config),
+                    build_alert_rules(objective.as_ref(), config),
                 ]
                 .into_iter()
             })
         })
-        .collect::<Vec<_>>()
-        .join("\n");
+        .collect()
+}
+
+/// Returns the Prometheus recording and alerting rules as a YAML string.
+///
+/// To generate alerts, add the `alerts` parameter to the `autometrics` macro
+/// for at least one function.
+///
+/// Then, call this function to generate the Prometheus rules. You will need
+/// to output the rules to a file and
+/// [load them into Prometheus](https://prometheus.io/docs/prometheus/latest/configuration/recording_rules/).
+pub fn generate_alerts() -> String {
+    generate_alerts_with_config(&AlertsConfig::default())
+}
+
+/// Like [`generate_alerts`], but uses the supplied [`AlertsConfig`] instead of
+/// the defaults, so teams can tune the rolling period, recording windows, and
+/// burn-rate ladder to their own error-budget policy.
+pub fn generate_alerts_with_config(config: &AlertsConfig) -> String {
+    let document = RuleDocument {
+        groups: rule_groups(config),
+    };
+    let groups = serde_yaml::to_string(&document).expect("rule groups are always serializable");
     format!(
         "---
 # Prometheus recording and alerting rules generated by autometrics-rs
 
-groups:
 {groups}"
     )
 }
 
+/// Returns the same rule groups as [`generate_alerts`], but serialized as JSON
+/// for tools that ingest rule definitions programmatically.
+pub fn generate_alerts_json() -> String {
+    generate_alerts_json_with_config(&AlertsConfig::default())
+}
+
+/// Like [`generate_alerts_json`], but uses the supplied [`AlertsConfig`].
+pub fn generate_alerts_json_with_config(config: &AlertsConfig) -> String {
+    let document = RuleDocument {
+        groups: rule_groups(config),
+    };
+    serde_json::to_string_pretty(&document).expect("rule groups are always serializable")
+}
+
+/// Returns a [Sloth](https://sloth.dev) SLO specification as a YAML string.
+///
+/// Unlike [`generate_alerts`], which bakes the full multi-window burn-rate
+/// computation into pre-expanded Prometheus rules, this emits the higher-level
+/// Sloth `prometheus/v1` spec. Users who already run Sloth as their rule
+/// compiler can feed these objectives into their existing pipeline instead of
+/// loading our hardcoded rules directly.
+///
+/// One document (separated by `---`) is emitted per annotated function, with a
+/// `service` derived from the module and one `slos` entry per objective.
+pub fn generate_sloth_spec() -> String {
+    METRICS
+        .iter()
+        .map(|alert| {
+            let slos = alert
+                .to_objectives()
+                .map(|objective| objective.sloth_slo())
+                .collect::<Vec<_>>()
+                .join("");
+            format!(
+                "---
+version: prometheus/v1
+service: {module}
+slos:
+{slos}",
+                module = alert.module,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("")
+}
+
 trait Objective {
     fn slo_type(&self) -> &'static str;
     fn function(&self) -> &'static str;
@@ -82,6 +388,87 @@ trait Objective {
         format!("{}-{}", self.module(), self.function())
     }
 
+    /// Static user-defined labels to merge into every generated rule.
+    fn extra_labels(&self) -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// The alerting method this objective selects, overriding
+    /// [`AlertsConfig::method`]. `None` falls back to the config default.
+    fn alert_method(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// The success-rate objective expressed as a percentage (e.g. `99.9`), as
+    /// Sloth expects it.
+    fn objective_percent(&self) -> String {
+        match self.success_rate().parse::<f64>() {
+            Ok(ratio) => format!("{}", ratio * 100.0),
+            // Fall back to the raw value if it isn't a plain ratio.
+            Err(_) => self.success_rate().to_string(),
+        }
+    }
+
+    /// Render this objective as a single Sloth `slos` entry. The error/total
+    /// queries reuse the same templates as the recording rules, but leave the
+    /// range selector as a `{{.window}}` placeholder for Sloth to expand.
+    ///
+    /// The `prometheus/v1` spec requires every SLO to carry an `alerting` block
+    /// with at least a `name`, so we emit one: the `page_alert`/`ticket_alert`
+    /// pair mirrors our own `page`/`ticket` severity tiers. User-defined labels
+    /// ([`extra_labels`](Objective::extra_labels)) are propagated into both the
+    /// SLO `labels` and the alert `labels` so routing survives the Sloth path.
+    fn sloth_slo(&self) -> String {
+        let id = self.id();
+        let slo_type = self.slo_type();
+        let objective = self.objective_percent();
+        let error_query = self.error_query("{{.window}}");
+        let total_query = self.total_query("{{.window}}");
+        let slo_labels = self.sloth_labels("    ");
+        let alert_labels = self.sloth_label_entries("          ");
+        format!(
+            "  - name: {id}-{slo_type}
+    objective: {objective}
+{slo_labels}    sli:
+      events:
+        error_query: {error_query}
+        total_query: {total_query}
+    alerting:
+      name: HighErrorRate-{id}-{slo_type}
+      page_alert:
+        labels:
+          severity: page
+{alert_labels}      ticket_alert:
+        labels:
+          severity: ticket
+{alert_labels}"
+        )
+    }
+
+    /// Render this objective's user-defined [`extra_labels`](Objective::extra_labels)
+    /// as a `labels:` block indented by `indent`, or the empty string when there
+    /// are none. Key/value lines are indented two further spaces.
+    fn sloth_labels(&self, indent: &str) -> String {
+        let extra = self.extra_labels();
+        if extra.is_empty() {
+            return String::new();
+        }
+        let mut block = format!("{indent}labels:\n");
+        block.push_str(&self.sloth_label_entries(&format!("{indent}  ")));
+        block
+    }
+
+    /// Render the user-defined [`extra_labels`](Objective::extra_labels) as bare
+    /// `key: value` lines at `indent`, for merging under an existing `labels:`
+    /// key. Empty when there are no user labels.
+    fn sloth_label_entries(&self, indent: &str) -> String {
+        let mut entries = String::new();
+        for (key, value) in self.extra_labels() {
+            entries.push_str(&format!("{indent}{key}: {value}\n"));
+        }
+        entries
+    }
+
     fn filter_labels(&self) -> String {
         let function = self.function();
         let module = self.module();
@@ -89,133 +476,260 @@ trait Objective {
         format!("{{function=\"{function}\",module=\"{module}\",objective=\"{slo_type}\"}}")
     }
 
-    /// When we create a new recording rule, attach these labels to it
-    fn recording_labels(&self) -> String {
-        let slo_type = self.slo_type();
-        let function = self.function();
-        let module = self.module();
-        format!(
-            "labels:
-      objective: {slo_type}
-      function: {function}
-      module: {module}",
-        )
+    /// The labels attached to every recording rule we create: the identifying
+    /// `objective`/`function`/`module` plus any user-defined labels.
+    fn recording_labels(&self) -> BTreeMap<String, String> {
+        let mut labels = BTreeMap::new();
+        labels.insert("objective".to_string(), self.slo_type().to_string());
+        labels.insert("function".to_string(), self.function().to_string());
+        labels.insert("module".to_string(), self.module().to_string());
+        for (key, value) in self.extra_labels() {
+            labels.insert(key.to_string(), value.to_string());
+        }
+        labels
     }
 
     /// Create recording rules for the error rate at each window
-    fn error_ratio_recording_rules(&self) -> String {
+    fn error_ratio_recording_rules(&self, config: &AlertsConfig) -> RuleGroup {
         let id = self.id();
         let slo_type = self.slo_type();
-        let recording_labels = self.recording_labels();
         let filter_labels = self.filter_labels();
-        let mut rules = format!(
-            "- name: autometrics-slo-sli-recordings-{id}-{slo_type}
-  rules:\n"
-        );
+        let period = config.period();
+        let base_window = config.base_window();
+        let mut rules = Vec::new();
 
-        for window in ["5m", "30m", "1h", "2h", "6h", "1d", "3d"] {
+        for window in &config.windows {
             let errors = self.error_query(window);
             let total = self.total_query(window);
-            rules.push_str(&format!(
-                "  - record: slo:sli_error:ratio_rate{window}
-    expr: {errors} / {total}
-    {recording_labels}
-      window: {window}\n"
-            ));
+            let mut labels = self.recording_labels();
+            labels.insert("window".to_string(), window.to_string());
+            rules.push(Rule::Record {
+                record: format!("slo:sli_error:ratio_rate{window}"),
+                expr: format!("{errors} / {total}"),
+                labels,
+            });
         }
 
-        // 30d query is a bit different
-        rules.push_str(&format!(
-            "  - record: slo:sli_error:ratio_rate30d
-    expr: |
-      sum_over_time(slo:sli_error:ratio_rate5m{filter_labels}[30d])
-      / ignoring(window)
-      count_over_time(slo:sli_error:ratio_rate5m{filter_labels}[30d])
-    {recording_labels}
-      window: 30d\n"
-        ));
+        // The long-term query is a bit different: it averages the base window's
+        // ratio over the whole rolling period.
+        let mut period_labels = self.recording_labels();
+        period_labels.insert("window".to_string(), period.clone());
+        rules.push(Rule::Record {
+            record: format!("slo:sli_error:ratio_rate{period}"),
+            expr: format!(
+                "sum_over_time(slo:sli_error:ratio_rate{base_window}{filter_labels}[{period}])\n\
+                 / ignoring(window)\n\
+                 count_over_time(slo:sli_error:ratio_rate{base_window}{filter_labels}[{period}])\n"
+            ),
+            labels: period_labels,
+        });
 
-        rules
+        RuleGroup {
+            name: format!("autometrics-slo-sli-recordings-{id}-{slo_type}"),
+            interval: config.interval.map(str::to_string),
+            rules,
+        }
     }
 
     /// Create the recording rules for the burn rate and error budget
-    fn meta_recording_rules(&self) -> String {
-        let recording_labels = self.recording_labels();
+    fn meta_recording_rules(&self, config: &AlertsConfig) -> RuleGroup {
         let filter_labels = self.filter_labels();
         let id = self.id();
         let slo_type = self.slo_type();
         let success_rate = self.success_rate();
-        format!(
-            "- name: autometrics-slo-meta-recordings-{id}-{slo_type}
-  rules:
-  - record: slo:objective:ratio
-    expr: vector({success_rate})
-    {recording_labels}
-  - record: slo:error_budget:ratio
-    expr: vector(1 - {success_rate})
-    {recording_labels}
-  - record: slo:time_period:days
-    expr: vector(30)
-    {recording_labels}
-  - record: slo:current_burn_rate:ratio
-    expr: slo:sli_error:ratio_rate5m{filter_labels} / on(function, module, objective) group_left slo:error_budget:ratio{filter_labels}
-    {recording_labels}
-  - record: slo:period_burn_rate:ratio
-    expr: slo:sli_error:ratio_rate30d{filter_labels} / on(function, module, objective) group_left slo:error_budget:ratio{filter_labels}
-    {recording_labels}
-  - record: slo:period_error_budget_remaining:ratio
-    expr: 1 - slo:period_burn_rate:ratio{filter_labels}
-    {recording_labels}\n")
-    }
-
-    /// Create the alert definitions for the SLO
-    fn alert_rules(&self) -> String {
-        let error_rate = format!("(1 - {})", self.success_rate());
-        let labels = self.filter_labels();
-        let id = self.id();
+        let period = config.period();
+        let period_days = config.period_days;
+        let base_window = config.base_window();
+        let record = |name: &str, expr: String| Rule::Record {
+            record: name.to_string(),
+            expr,
+            labels: self.recording_labels(),
+        };
+        RuleGroup {
+            name: format!("autometrics-slo-meta-recordings-{id}-{slo_type}"),
+            interval: config.interval.map(str::to_string),
+            rules: vec![
+                record("slo:objective:ratio", format!("vector({success_rate})")),
+                record("slo:error_budget:ratio", format!("vector(1 - {success_rate})")),
+                record("slo:time_period:days", format!("vector({period_days})")),
+                record(
+                    "slo:current_burn_rate:ratio",
+                    format!("slo:sli_error:ratio_rate{base_window}{filter_labels} / on(function, module, objective) group_left slo:error_budget:ratio{filter_labels}"),
+                ),
+                record(
+                    "slo:period_burn_rate:ratio",
+                    format!("slo:sli_error:ratio_rate{period}{filter_labels} / on(function, module, objective) group_left slo:error_budget:ratio{filter_labels}"),
+                ),
+                record(
+                    "slo:period_error_budget_remaining:ratio",
+                    format!("1 - slo:period_burn_rate:ratio{filter_labels}"),
+                ),
+            ],
+        }
+    }
+
+    /// The `labels` and `annotations` blocks shared by every alert rule for this
+    /// objective: the `severity` plus the user-defined routing labels.
+    fn alert_metadata(&self, severity: &str) -> (BTreeMap<String, String>, BTreeMap<String, String>) {
         let function = self.function();
         let module = self.module();
-        let slo_type = self.slo_type();
-        format!(
-            "- name: autometrics-slo-alerts-{id}-{slo_type}
-  rules:
-  - alert: HighErrorRate-{id}-{slo_type}
-    expr: |
-      (
-        max(slo:sli_error:ratio_rate5m{labels} > (14.4 * {error_rate}))
-        and
-        max(slo:sli_error:ratio_rate1h{labels} > (14.4 * {error_rate}))
-      )
-      or
-      (
-        max(slo:sli_error:ratio_rate30m{labels} > (6 * {error_rate}))
-        and
-        max(slo:sli_error:ratio_rate6h{labels} > (6 * {error_rate}))
-      )
-    labels:
-      severity: page
-    annotations:
-      summary: High error rate for function '{function}' in module '{module}'
-      title: (page) '{function}' in module '{module}' SLO error budget burn rate is too fast.
-  - alert: HighErrorRate-{id}-{slo_type}
-    expr: |
-      (
-        max(slo:sli_error:ratio_rate2h{labels} > (3 * {error_rate}))
-        and
-        max(slo:sli_error:ratio_rate1d{labels} > (3 * {error_rate}))
-      )
-      or
-      (
-        max(slo:sli_error:ratio_rate6h{labels} > (1 * {error_rate}))
-        and
-        max(slo:sli_error:ratio_rate3d{labels} > (1 * {error_rate}))
-      )
-    labels:
-      severity: ticket
-    annotations:
-      summary: High error rate for function '{function}' in module '{module}'
-      title: (ticket) '{function}' in module '{module}' SLO error budget burn rate is too fast.\n"
-        )
+
+        let mut labels = BTreeMap::new();
+        labels.insert("severity".to_string(), severity.to_string());
+
+        let mut annotations = BTreeMap::new();
+        annotations.insert(
+            "summary".to_string(),
+            format!("High error rate for function '{function}' in module '{module}'"),
+        );
+        annotations.insert(
+            "title".to_string(),
+            format!("({severity}) '{function}' in module '{module}' SLO error budget burn rate is too fast."),
+        );
+
+        // User-defined labels are routing metadata, so merge them into both the
+        // labels and annotations blocks.
+        for (key, value) in self.extra_labels() {
+            labels.insert(key.to_string(), value.to_string());
+            annotations.insert(key.to_string(), value.to_string());
+        }
+
+        (labels, annotations)
+    }
+}
+
+/// The default [`AlertMethod`] name, selected when nothing else overrides it.
+pub const DEFAULT_ALERT_METHOD: &str = "multi-window";
+
+/// Create the alert definitions for an objective's SLO.
+///
+/// The individual rules are produced by the [`AlertMethod`] selected for the
+/// objective (or, failing that, by [`AlertsConfig::method`]); this function
+/// only wraps them in the enclosing group. It takes `&dyn Objective` directly
+/// rather than being a default trait method so the already-`dyn` objective from
+/// [`rule_groups`] can be forwarded to the method without an unsizing coercion.
+fn build_alert_rules(objective: &dyn Objective, config: &AlertsConfig) -> RuleGroup {
+    let id = objective.id();
+    let slo_type = objective.slo_type();
+    let method = alert_method(objective.alert_method().unwrap_or(config.method));
+    RuleGroup {
+        name: format!("autometrics-slo-alerts-{id}-{slo_type}"),
+        interval: config.interval.map(str::to_string),
+        rules: method.alert_rules(objective, config),
+    }
+}
+
+/// A strategy for turning an objective's burn-rate ladder into Prometheus
+/// alerting rules.
+///
+/// Following the approach of
+/// [slo-generator](https://github.com/google/slo-generator), the method is
+/// selected by name rather than wired in, so teams can pick the behaviour that
+/// matches their traffic profile.
+trait AlertMethod {
+    fn alert_rules(&self, objective: &dyn Objective, config: &AlertsConfig) -> Vec<Rule>;
+}
+
+/// Resolve an [`AlertMethod`] by name, falling back to the default when the
+/// name is unknown.
+fn alert_method(name: &str) -> Box<dyn AlertMethod> {
+    match name {
+        "single-window" => Box::new(SingleWindowMethod),
+        // Unknown names fall back to the Google SRE ladder.
+        _ => Box::new(MultiWindowMethod),
+    }
+}
+
+/// The Google SRE multi-window, multi-burn-rate ladder: each tier fires only
+/// when a short *and* a long window both exceed the burn rate.
+struct MultiWindowMethod;
+
+impl AlertMethod for MultiWindowMethod {
+    fn alert_rules(&self, objective: &dyn Objective, config: &AlertsConfig) -> Vec<Rule> {
+        let error_rate = format!("(1 - {})", objective.success_rate());
+        let labels = objective.filter_labels();
+        let id = objective.id();
+        let slo_type = objective.slo_type();
+
+        config
+            .severity_tiers
+            .iter()
+            .map(|tier| {
+                let expr = tier
+                    .burn_rates
+                    .iter()
+                    .map(|burn_rate| {
+                        let BurnRate {
+                            short_window,
+                            long_window,
+                            factor,
+                        } = burn_rate;
+                        format!(
+                            "(\n  \
+                               max(slo:sli_error:ratio_rate{short_window}{labels} > ({factor} * {error_rate}))\n  \
+                               and\n  \
+                               max(slo:sli_error:ratio_rate{long_window}{labels} > ({factor} * {error_rate}))\n\
+                             )"
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\nor\n");
+
+                let (labels, annotations) = objective.alert_metadata(tier.severity);
+                Rule::Alert {
+                    alert: format!("HighErrorRate-{id}-{slo_type}"),
+                    expr,
+                    for_: None,
+                    labels,
+                    annotations,
+                }
+            })
+            .collect()
+    }
+}
+
+/// A simpler single-short-window method for low-traffic functions, where the
+/// long/short AND-joins of the multi-window ladder rarely fire together. Each
+/// tier alerts on its short window alone, dropping the long-window conjunct.
+struct SingleWindowMethod;
+
+impl AlertMethod for SingleWindowMethod {
+    fn alert_rules(&self, objective: &dyn Objective, config: &AlertsConfig) -> Vec<Rule> {
+        let error_rate = format!("(1 - {})", objective.success_rate());
+        let labels = objective.filter_labels();
+        let id = objective.id();
+        let slo_type = objective.slo_type();
+
+        config
+            .severity_tiers
+            .iter()
+            .map(|tier| {
+                let expr = tier
+                    .burn_rates
+                    .iter()
+                    .map(|burn_rate| {
+                        let BurnRate {
+                            short_window,
+                            factor,
+                            ..
+                        } = burn_rate;
+                        format!(
+                            "max(slo:sli_error:ratio_rate{short_window}{labels} > ({factor} * {error_rate}))"
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\nor\n");
+
+                let (labels, annotations) = objective.alert_metadata(tier.severity);
+                Rule::Alert {
+                    alert: format!("HighErrorRate-{id}-{slo_type}"),
+                    expr,
+                    for_: None,
+                    labels,
+                    annotations,
+                }
+            })
+            .collect()
     }
 }
 
@@ -223,6 +737,9 @@ struct SuccessRateObjective {
     function: &'static str,
     module: &'static str,
     success_rate: &'static str,
+    labels: &'static [(&'static str, &'static str)],
+    alert_method: Option<&'static str>,
+    code: Option<CodeClassification>,
 }
 
 impl Objective for SuccessRateObjective {
@@ -230,6 +747,14 @@ impl Objective for SuccessRateObjective {
         "success-rate"
     }
 
+    fn extra_labels(&self) -> &'static [(&'static str, &'static str)] {
+        self.labels
+    }
+
+    fn alert_method(&self) -> Option<&'static str> {
+        self.alert_method
+    }
+
     fn function(&self) -> &'static str {
         self.function
     }
@@ -245,13 +770,21 @@ impl Objective for SuccessRateObjective {
     fn error_query(&self, window: &str) -> String {
         let function = self.function();
         let module = self.module();
-        format!("sum(rate(function_calls_count{{function=\"{function}\",module=\"{module}\",result=\"error\"}}[{window}]))")
+        // With a code classification we count the codes matching `error_matcher`
+        // (minus any excluded codes); otherwise any `result="error"` counts.
+        let error_selector = match &self.code {
+            Some(code) => format!("{}=~\"{}\"{}", code.label, code.error_matcher, code.exclusion_selector()),
+            None => "result=\"error\"".to_string(),
+        };
+        format!("sum(rate(function_calls_count{{function=\"{function}\",module=\"{module}\",{error_selector}}}[{window}]))")
     }
 
     fn total_query(&self, window: &str) -> String {
         let function = self.function();
         let module = self.module();
-        format!("sum(rate(function_calls_count{{function=\"{function}\",module=\"{module}\"}}[{window}]))")
+        // Excluded codes (e.g. 4xx client errors) don't count toward the budget.
+        let exclusion = self.code.map(|code| code.exclusion_selector()).unwrap_or_default();
+        format!("sum(rate(function_calls_count{{function=\"{function}\",module=\"{module}\"{exclusion}}}[{window}]))")
     }
 }
 
@@ -260,6 +793,9 @@ struct LatencyObjective {
     module: &'static str,
     latency_objective: &'static str,
     latency_threshold: &'static str,
+    labels: &'static [(&'static str, &'static str)],
+    alert_method: Option<&'static str>,
+    code: Option<CodeClassification>,
 }
 
 impl Objective for LatencyObjective {
@@ -267,6 +803,14 @@ impl Objective for LatencyObjective {
         "latency"
     }
 
+    fn extra_labels(&self) -> &'static [(&'static str, &'static str)] {
+        self.labels
+    }
+
+    fn alert_method(&self) -> Option<&'static str> {
+        self.alert_method
+    }
+
     fn function(&self) -> &'static str {
         self.function
     }
@@ -283,13 +827,170 @@ impl Objective for LatencyObjective {
         let function = self.function();
         let module = self.module();
         let latency_threshold = self.latency_threshold;
-        format!("(sum(rate(function_calls_duration_bucket{{function=\"{function}\",module=\"{module}\"}}[{window}])) \
-                - sum(rate(function_calls_duration_bucket{{le=\"{latency_threshold}\",function=\"{function}\",module=\"{module}\"}}[{window}])))")
+        // Error responses and any excluded codes shouldn't weigh on latency.
+        let exclusion = self.code.map(|code| code.latency_selector()).unwrap_or_default();
+        format!("(sum(rate(function_calls_duration_bucket{{function=\"{function}\",module=\"{module}\"{exclusion}}}[{window}])) \
+                - sum(rate(function_calls_duration_bucket{{le=\"{latency_threshold}\",function=\"{function}\",module=\"{module}\"{exclusion}}}[{window}])))")
     }
 
     fn total_query(&self, window: &str) -> String {
         let function = self.function();
         let module = self.module();
-        format!("sum(rate(function_calls_duration_bucket{{function=\"{function}\",module=\"{module}\"}}[{window}]))")
+        let exclusion = self.code.map(|code| code.latency_selector()).unwrap_or_default();
+        format!("sum(rate(function_calls_duration_bucket{{function=\"{function}\",module=\"{module}\"{exclusion}}}[{window}]))")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn success_rate(code: Option<CodeClassification>) -> SuccessRateObjective {
+        SuccessRateObjective {
+            function: "my_function",
+            module: "my_module",
+            success_rate: "0.99",
+            labels: &[],
+            alert_method: None,
+            code,
+        }
+    }
+
+    fn latency(code: Option<CodeClassification>) -> LatencyObjective {
+        LatencyObjective {
+            function: "my_function",
+            module: "my_module",
+            latency_objective: "0.99",
+            latency_threshold: "0.5",
+            labels: &[],
+            alert_method: None,
+            code,
+        }
+    }
+
+    #[test]
+    fn success_rate_queries_without_code() {
+        let objective = success_rate(None);
+        assert_eq!(
+            objective.error_query("5m"),
+            "sum(rate(function_calls_count{function=\"my_function\",module=\"my_module\",result=\"error\"}[5m]))"
+        );
+        assert_eq!(
+            objective.total_query("5m"),
+            "sum(rate(function_calls_count{function=\"my_function\",module=\"my_module\"}[5m]))"
+        );
+    }
+
+    #[test]
+    fn success_rate_queries_with_code_no_exclusion() {
+        let objective = success_rate(Some(CodeClassification {
+            label: "status",
+            error_matcher: "5..",
+            exclude_matcher: None,
+        }));
+        assert_eq!(
+            objective.error_query("5m"),
+            "sum(rate(function_calls_count{function=\"my_function\",module=\"my_module\",status=~\"5..\"}[5m]))"
+        );
+        // Without an exclusion matcher the denominator is unchanged.
+        assert_eq!(
+            objective.total_query("5m"),
+            "sum(rate(function_calls_count{function=\"my_function\",module=\"my_module\"}[5m]))"
+        );
+    }
+
+    #[test]
+    fn success_rate_queries_with_code_and_exclusion() {
+        let objective = success_rate(Some(CodeClassification {
+            label: "status",
+            error_matcher: "5..",
+            exclude_matcher: Some("4.."),
+        }));
+        assert_eq!(
+            objective.error_query("5m"),
+            "sum(rate(function_calls_count{function=\"my_function\",module=\"my_module\",status=~\"5..\",status!~\"4..\"}[5m]))"
+        );
+        assert_eq!(
+            objective.total_query("5m"),
+            "sum(rate(function_calls_count{function=\"my_function\",module=\"my_module\",status!~\"4..\"}[5m]))"
+        );
+    }
+
+    #[test]
+    fn latency_queries_exclude_error_and_excluded_codes() {
+        let objective = latency(Some(CodeClassification {
+            label: "status",
+            error_matcher: "5..",
+            exclude_matcher: Some("4.."),
+        }));
+        // Error responses (5xx) and excluded codes (4xx) drop out of both the
+        // numerator and denominator.
+        assert_eq!(
+            objective.error_query("5m"),
+            "(sum(rate(function_calls_duration_bucket{function=\"my_function\",module=\"my_module\",status!~\"5..\",status!~\"4..\"}[5m])) \
+             - sum(rate(function_calls_duration_bucket{le=\"0.5\",function=\"my_function\",module=\"my_module\",status!~\"5..\",status!~\"4..\"}[5m])))"
+        );
+        assert_eq!(
+            objective.total_query("5m"),
+            "sum(rate(function_calls_duration_bucket{function=\"my_function\",module=\"my_module\",status!~\"5..\",status!~\"4..\"}[5m]))"
+        );
+    }
+
+    // A single registered alert drives the document-level golden tests below.
+    #[distributed_slice(METRICS)]
+    static GOLDEN_ALERT: Alert = Alert {
+        function: "my_function",
+        module: "my_module",
+        success_rate: Some("0.99"),
+        latency: None,
+        labels: &[],
+        alert_method: None,
+        code: None,
+    };
+
+    #[test]
+    fn generate_alerts_json_golden() {
+        let json = generate_alerts_json();
+        let document: serde_json::Value =
+            serde_json::from_str(&json).expect("output is valid JSON");
+        let groups = document["groups"].as_array().expect("groups is an array");
+
+        // One success-rate objective yields the sli, meta, and alert groups.
+        assert_eq!(groups.len(), 3);
+
+        let meta = groups
+            .iter()
+            .find(|group| {
+                group["name"]
+                    == "autometrics-slo-meta-recordings-my_module-my_function-success-rate"
+            })
+            .expect("meta group is present");
+        let time_period = meta["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|rule| rule["record"] == "slo:time_period:days")
+            .expect("time period rule is present");
+        assert_eq!(time_period["expr"], "vector(30)");
+        assert_eq!(time_period["labels"]["function"], "my_function");
+    }
+
+    #[test]
+    fn generate_sloth_spec_golden() {
+        let spec = generate_sloth_spec();
+        assert!(spec.contains("version: prometheus/v1"));
+        assert!(spec.contains("service: my_module"));
+        // The range selector is left as a Sloth placeholder.
+        assert!(spec.contains(
+            "error_query: sum(rate(function_calls_count{function=\"my_function\",module=\"my_module\",result=\"error\"}[{{.window}}]))"
+        ));
+        assert!(spec.contains(
+            "total_query: sum(rate(function_calls_count{function=\"my_function\",module=\"my_module\"}[{{.window}}]))"
+        ));
+        // Sloth requires an alerting block with a name on every SLO.
+        assert!(spec.contains("alerting:"));
+        assert!(spec.contains("name: HighErrorRate-my_module-my_function-success-rate"));
+        assert!(spec.contains("severity: page"));
+        assert!(spec.contains("severity: ticket"));
     }
 }
\ No newline at end of file